@@ -0,0 +1,15 @@
+//! Shared error type for the terminal and process management core.
+
+use thiserror::Error;
+
+/// Errors surfaced by the terminal/process management core.
+#[derive(Debug, Error)]
+pub enum PtyError {
+    /// A child process failed to spawn.
+    #[error("failed to spawn process: {0}")]
+    Spawn(#[source] std::io::Error),
+
+    /// A terminal session could not be found.
+    #[error("session not found: {0}")]
+    SessionNotFound(String),
+}
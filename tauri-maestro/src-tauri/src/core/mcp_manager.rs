@@ -0,0 +1,110 @@
+//! Launches and supervises MCP server child processes.
+//!
+//! `McpManager` owns the lifecycle of configured MCP servers: starting them,
+//! restarting them on demand, and shutting them down when a session ends.
+//! Every server is launched through [`McpManager::server_command`], which
+//! applies the manager's [`ProcessSpawnOptions`] so a restarted MCP server
+//! can't flash a console window on Windows GUI embeds.
+
+use crate::core::windows_process::{ManagedChild, ProcessGroup, ProcessSpawnOptions, WindowVisibility};
+use crate::core::PtyError;
+use tokio::process::Command;
+
+#[cfg(windows)]
+use crate::core::windows_process::HiddenChild;
+
+/// Launches and supervises MCP server processes.
+#[derive(Debug, Clone)]
+pub struct McpManager {
+    spawn_options: ProcessSpawnOptions,
+}
+
+impl McpManager {
+    /// Creates a new `McpManager` that applies `spawn_options` to every MCP
+    /// server command it launches.
+    pub fn new(spawn_options: ProcessSpawnOptions) -> Self {
+        Self { spawn_options }
+    }
+
+    /// Builds the command used to launch an MCP server, applying this
+    /// manager's spawn policy and `visibility`, and fully detaching it from
+    /// this process's console/session so a restart can take down the whole
+    /// tree instead of orphaning grandchildren.
+    fn server_command(&self, program: &str, args: &[String], visibility: WindowVisibility) -> Command {
+        let mut command = Command::new(program);
+        command.args(args);
+        self.spawn_options
+            .with_window_visibility(visibility)
+            .with_detached(true)
+            .apply_to_tokio(&mut command);
+        command
+    }
+
+    /// Launches an MCP server with `visibility`, returning the running child
+    /// process together with a [`ProcessGroup`] handle that can terminate
+    /// the whole tree — including a [`ManagedChild::Hidden`] one, since
+    /// `HiddenChild::spawn` roots its own `CREATE_NEW_PROCESS_GROUP` the same
+    /// way the ordinary `Command` path does.
+    ///
+    /// On Windows, [`WindowVisibility::HideAll`] fully hides a headless MCP
+    /// server (no console, no GUI window) via a raw `CreateProcessW` call;
+    /// passing [`WindowVisibility::Default`] instead still lets a
+    /// deliberately GUI MCP tool show its window when the session wants it.
+    pub fn start_server(
+        &self,
+        program: &str,
+        args: &[String],
+        visibility: WindowVisibility,
+    ) -> Result<(ManagedChild, ProcessGroup), PtyError> {
+        #[cfg(windows)]
+        if visibility == WindowVisibility::HideAll {
+            let hidden = HiddenChild::spawn(program, args).map_err(PtyError::Spawn)?;
+            let group = ProcessGroup::from_child_id(hidden.id());
+            return Ok((ManagedChild::Hidden(hidden), group));
+        }
+
+        let child = self
+            .server_command(program, args, visibility)
+            .spawn()
+            .map_err(PtyError::Spawn)?;
+        let group_id = child.id().expect("freshly spawned child has a pid");
+        Ok((ManagedChild::Command(child), ProcessGroup::from_child_id(group_id)))
+    }
+
+    /// Restarts an MCP server: terminates the whole process tree rooted at
+    /// `group`, then launches a fresh instance with `visibility`.
+    pub async fn restart_server(
+        &self,
+        group: &ProcessGroup,
+        program: &str,
+        args: &[String],
+        visibility: WindowVisibility,
+    ) -> Result<(ManagedChild, ProcessGroup), PtyError> {
+        group.terminate().map_err(PtyError::Spawn)?;
+        self.start_server(program, args, visibility)
+    }
+}
+
+#[cfg(all(test, windows))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn server_command_applies_window_visibility_and_detached_flags() {
+        let manager = McpManager::new(ProcessSpawnOptions::new());
+        let command = manager.server_command("mcp-server", &[], WindowVisibility::HideConsole);
+        let flags = std::os::windows::process::CommandExt::creation_flags(&command);
+        assert_eq!(
+            flags & crate::core::windows_process::CREATE_NO_WINDOW,
+            crate::core::windows_process::CREATE_NO_WINDOW
+        );
+        assert_eq!(
+            flags & crate::core::windows_process::CREATE_NEW_PROCESS_GROUP,
+            crate::core::windows_process::CREATE_NEW_PROCESS_GROUP
+        );
+        assert_eq!(
+            flags & crate::core::windows_process::DETACHED_PROCESS,
+            crate::core::windows_process::DETACHED_PROCESS
+        );
+    }
+}
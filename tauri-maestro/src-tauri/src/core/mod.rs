@@ -6,6 +6,7 @@ pub mod plugin_manager;
 pub mod process_manager;
 pub mod session_manager;
 pub mod terminal_backend;
+pub mod windows_process;
 pub mod worktree_manager;
 pub mod xterm_backend;
 
@@ -20,8 +21,9 @@ pub use process_manager::ProcessManager;
 pub use session_manager::SessionManager;
 pub use terminal_backend::{
     BackendCapabilities, BackendType, SubscriptionHandle, TerminalBackend, TerminalConfig,
-    TerminalError, TerminalState,
+    TerminalError, TerminalEvent, TerminalState, WindowManipulation,
 };
+pub use windows_process::{ManagedChild, ProcessGroup, ProcessSpawnOptions, WindowVisibility};
 pub use worktree_manager::WorktreeManager;
 pub use xterm_backend::XtermPassthroughBackend;
 
@@ -0,0 +1,112 @@
+//! Spawns and tracks the lifetime of child processes owned by a terminal session.
+//!
+//! `ProcessManager` is the single place that builds `Command`s for ad-hoc child
+//! processes (as opposed to the long-lived PTY sessions owned by `SessionManager`).
+//! It applies the crate-wide [`ProcessSpawnOptions`] policy to every command it
+//! constructs so callers never have to remember to hide console windows by hand.
+
+use crate::core::windows_process::{ManagedChild, ProcessGroup, ProcessSpawnOptions, WindowVisibility};
+use std::process::ExitStatus;
+use tokio::process::Command as TokioCommand;
+
+#[cfg(windows)]
+use crate::core::windows_process::HiddenChild;
+
+/// Spawns and supervises child processes on behalf of a terminal session.
+#[derive(Debug, Clone)]
+pub struct ProcessManager {
+    spawn_options: ProcessSpawnOptions,
+}
+
+impl ProcessManager {
+    /// Creates a new `ProcessManager` that applies `spawn_options` to every
+    /// command it builds.
+    pub fn new(spawn_options: ProcessSpawnOptions) -> Self {
+        Self { spawn_options }
+    }
+
+    /// Builds a `tokio::process::Command` for `program`, applying this
+    /// manager's spawn policy.
+    fn command(&self, program: &str) -> TokioCommand {
+        let mut command = TokioCommand::new(program);
+        self.spawn_options.apply_to_tokio(&mut command);
+        command
+    }
+
+    /// Spawns a trivial, short-lived process used to exercise the spawn
+    /// policy in tests (`cmd /C exit 0` on Windows, `true` elsewhere).
+    #[cfg(windows)]
+    pub async fn spawn_dummy(&self) -> std::io::Result<ExitStatus> {
+        let mut command = self.command("cmd");
+        command.args(["/C", "exit", "0"]);
+        command.status().await
+    }
+
+    /// Spawns a trivial, short-lived process used to exercise the spawn
+    /// policy in tests (`cmd /C exit 0` on Windows, `true` elsewhere).
+    #[cfg(not(windows))]
+    pub async fn spawn_dummy(&self) -> std::io::Result<ExitStatus> {
+        self.command("true").status().await
+    }
+
+    /// Spawns `program` fully detached from this process's console/session,
+    /// so that ending the owning terminal session can take down the whole
+    /// child tree at once instead of orphaning grandchildren. Returns the
+    /// child alongside a [`ProcessGroup`] handle that can terminate the
+    /// whole tree.
+    pub fn spawn_tracked(
+        &self,
+        program: &str,
+        args: &[String],
+    ) -> std::io::Result<(tokio::process::Child, ProcessGroup)> {
+        let mut command = TokioCommand::new(program);
+        command.args(args);
+        self.spawn_options
+            .with_detached(true)
+            .apply_to_tokio(&mut command);
+        let child = command.spawn()?;
+        let group_id = child.id().expect("freshly spawned child has a pid");
+        Ok((child, ProcessGroup::from_child_id(group_id)))
+    }
+
+    /// Spawns `program`, applying `visibility` on top of this manager's
+    /// baseline spawn policy. On Windows, [`WindowVisibility::HideAll`]
+    /// bypasses the ordinary `Command` path entirely in favor of a raw
+    /// `CreateProcessW` call that can suppress a GUI window, returning
+    /// [`ManagedChild::Hidden`]; every other case returns
+    /// [`ManagedChild::Command`].
+    pub fn spawn_with_visibility(
+        &self,
+        program: &str,
+        args: &[String],
+        visibility: WindowVisibility,
+    ) -> std::io::Result<ManagedChild> {
+        #[cfg(windows)]
+        if visibility == WindowVisibility::HideAll {
+            return HiddenChild::spawn(program, args).map(ManagedChild::Hidden);
+        }
+
+        let mut command = TokioCommand::new(program);
+        command.args(args);
+        self.spawn_options
+            .with_window_visibility(visibility)
+            .apply_to_tokio(&mut command);
+        command.spawn().map(ManagedChild::Command)
+    }
+}
+
+#[cfg(all(test, windows))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_applies_this_managers_hide_console_policy() {
+        let manager = ProcessManager::new(ProcessSpawnOptions::new().with_hide_console_window(true));
+        let command = manager.command("cmd");
+        assert_eq!(
+            std::os::windows::process::CommandExt::creation_flags(&command)
+                & crate::core::windows_process::CREATE_NO_WINDOW,
+            crate::core::windows_process::CREATE_NO_WINDOW
+        );
+    }
+}
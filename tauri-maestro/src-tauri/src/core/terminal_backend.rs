@@ -0,0 +1,289 @@
+//! Defines the pluggable terminal backend abstraction.
+//!
+//! A [`TerminalBackend`] owns a PTY-backed session and exposes its output as
+//! a stream of [`TerminalEvent`]s that a frontend or host GUI can subscribe
+//! to. Different backends trade fidelity for portability:
+//! `XtermPassthroughBackend` forwards raw bytes to an xterm.js frontend,
+//! while the optional `VteBackend` is meant for hosts with no frontend VT
+//! parser of their own to forward to.
+
+use thiserror::Error;
+
+/// Identifies which concrete backend is in use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendType {
+    /// Forwards raw bytes to an xterm.js frontend; the frontend does all VT parsing.
+    XtermPassthrough,
+    /// Parses window-manipulation sequences out of the stream server-side
+    /// instead of forwarding raw bytes to a frontend parser.
+    #[cfg(feature = "vte-backend")]
+    Vte,
+}
+
+/// Capabilities a backend negotiates with its caller before a session starts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BackendCapabilities {
+    /// Whether the backend intercepts window-manipulation escape sequences
+    /// (minimize/restore/title) and surfaces them as [`TerminalEvent::WindowManipulation`]
+    /// instead of silently passing them through as raw output.
+    pub supports_window_manipulation: bool,
+}
+
+/// Configuration used to start a terminal session.
+#[derive(Debug, Clone)]
+pub struct TerminalConfig {
+    /// Initial terminal height, in rows.
+    pub rows: u16,
+    /// Initial terminal width, in columns.
+    pub cols: u16,
+    /// Shell to launch; `None` uses the platform default.
+    pub shell: Option<String>,
+}
+
+impl Default for TerminalConfig {
+    fn default() -> Self {
+        Self {
+            rows: 24,
+            cols: 80,
+            shell: None,
+        }
+    }
+}
+
+/// Snapshot of a terminal session's lifecycle state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalState {
+    /// The child process has been requested but has not yet been confirmed running.
+    Starting,
+    /// The child process is running.
+    Running,
+    /// The child process has exited.
+    Exited,
+}
+
+/// Errors a terminal backend can report.
+#[derive(Debug, Error)]
+pub enum TerminalError {
+    /// The backend failed to start the session.
+    #[error("failed to start terminal session: {0}")]
+    Spawn(#[source] std::io::Error),
+    /// An operation was attempted on a session that already exited.
+    #[error("terminal session already exited")]
+    AlreadyExited,
+}
+
+/// Opaque handle returned when subscribing to a backend's event stream;
+/// dropping it unsubscribes.
+#[derive(Debug)]
+pub struct SubscriptionHandle {
+    id: u64,
+}
+
+impl SubscriptionHandle {
+    /// Creates a handle identified by `id`, unique among a backend's live subscriptions.
+    pub fn new(id: u64) -> Self {
+        Self { id }
+    }
+
+    /// Returns this subscription's id.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+}
+
+/// A window-manipulation intent, translated to or from the nearest portable
+/// VT escape sequence rather than a real Win32 `HWND`. Command-line tools
+/// that call `GetConsoleWindow()` + `ShowWindow()`/`SetWindowText()` have
+/// nothing to manipulate under a PTY-backed session, but well-behaved
+/// terminal programs fall back to emitting these VT sequences directly, so a
+/// backend can recover the same intent by parsing its output stream for them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowManipulation {
+    /// `CSI 2 t` — minimize/hide the window.
+    Hide,
+    /// `CSI 1 t` — restore/show the window.
+    Show,
+    /// `OSC 0 ; text BEL` — set the window title.
+    SetTitle(String),
+}
+
+impl WindowManipulation {
+    /// Encodes this intent as its VT escape sequence.
+    pub fn to_vt_sequence(&self) -> Vec<u8> {
+        match self {
+            WindowManipulation::Hide => b"\x1b[2t".to_vec(),
+            WindowManipulation::Show => b"\x1b[1t".to_vec(),
+            WindowManipulation::SetTitle(title) => format!("\x1b]0;{title}\x07").into_bytes(),
+        }
+    }
+
+    /// Parses a single window-manipulation sequence at the start of `bytes`,
+    /// returning the intent and the number of bytes it consumed.
+    pub fn parse(bytes: &[u8]) -> Option<(Self, usize)> {
+        if bytes.starts_with(b"\x1b[2t") {
+            Some((WindowManipulation::Hide, 4))
+        } else if bytes.starts_with(b"\x1b[1t") {
+            Some((WindowManipulation::Show, 4))
+        } else if let Some(rest) = bytes.strip_prefix(b"\x1b]0;") {
+            let end = rest.iter().position(|&b| b == 0x07)?;
+            let title = String::from_utf8_lossy(&rest[..end]).into_owned();
+            Some((WindowManipulation::SetTitle(title), 4 + end + 1))
+        } else {
+            None
+        }
+    }
+}
+
+/// An event delivered to a subscriber of a backend's output stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TerminalEvent {
+    /// Raw output bytes from the child process.
+    Output(Vec<u8>),
+    /// A window-manipulation intent recovered from the output stream, for
+    /// backends with [`BackendCapabilities::supports_window_manipulation`] set.
+    WindowManipulation(WindowManipulation),
+}
+
+/// Returns whether `data` could still be the start of a recognized
+/// window-manipulation sequence if more bytes arrive, and should therefore
+/// be held back rather than flushed as output.
+///
+/// A title change (`OSC 0 ; text BEL`) has no fixed length, so anything
+/// that starts with the `OSC 0 ;` prefix is treated as incomplete until a
+/// terminating BEL shows up.
+fn is_incomplete_sequence_prefix(data: &[u8]) -> bool {
+    const HIDE: &[u8] = b"\x1b[2t";
+    const SHOW: &[u8] = b"\x1b[1t";
+    const TITLE_PREFIX: &[u8] = b"\x1b]0;";
+
+    if HIDE.starts_with(data) || SHOW.starts_with(data) {
+        return true;
+    }
+    if data.len() <= TITLE_PREFIX.len() {
+        return TITLE_PREFIX.starts_with(data);
+    }
+    data.starts_with(TITLE_PREFIX) && !data[TITLE_PREFIX.len()..].contains(&0x07)
+}
+
+/// Splits raw child output into a sequence of events, extracting
+/// window-manipulation sequences as their own events and forwarding
+/// everything else as [`TerminalEvent::Output`].
+///
+/// Shared by every backend that recovers window-manipulation intents by
+/// byte-scanning for [`WindowManipulation::parse`] matches rather than
+/// driving a full VT parser, since the sequences of interest are a small,
+/// fixed set that doesn't need one.
+///
+/// Real PTY reads aren't sequence-aligned: a `CSI 2 t` or `OSC 0 ; text BEL`
+/// can land split across two reads. `pending` is the caller's buffer for
+/// exactly that case — an unmatched tail that could still be a prefix of a
+/// recognized sequence is appended to `pending` and held back instead of
+/// being flushed as output, to be completed (or given up on, once it can no
+/// longer be a prefix of anything) on the next call.
+pub fn scan_for_window_manipulation(pending: &mut Vec<u8>, bytes: &[u8]) -> Vec<TerminalEvent> {
+    pending.extend_from_slice(bytes);
+
+    let mut events = Vec::new();
+    let mut pending_output = Vec::new();
+    let mut data = pending.as_slice();
+    while !data.is_empty() {
+        if let Some((intent, consumed)) = WindowManipulation::parse(data) {
+            if !pending_output.is_empty() {
+                events.push(TerminalEvent::Output(std::mem::take(&mut pending_output)));
+            }
+            events.push(TerminalEvent::WindowManipulation(intent));
+            data = &data[consumed..];
+        } else if is_incomplete_sequence_prefix(data) {
+            break;
+        } else {
+            pending_output.push(data[0]);
+            data = &data[1..];
+        }
+    }
+    if !pending_output.is_empty() {
+        events.push(TerminalEvent::Output(pending_output));
+    }
+
+    let consumed = pending.len() - data.len();
+    pending.drain(..consumed);
+    events
+}
+
+/// A pluggable terminal session backend.
+pub trait TerminalBackend {
+    /// Returns which concrete backend this is.
+    fn backend_type(&self) -> BackendType;
+
+    /// Returns the capabilities this backend negotiates with callers.
+    fn capabilities(&self) -> BackendCapabilities;
+
+    /// Returns the current lifecycle state of the session.
+    fn state(&self) -> TerminalState;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_hide_and_show() {
+        assert_eq!(
+            WindowManipulation::parse(&WindowManipulation::Hide.to_vt_sequence()),
+            Some((WindowManipulation::Hide, 4))
+        );
+        assert_eq!(
+            WindowManipulation::parse(&WindowManipulation::Show.to_vt_sequence()),
+            Some((WindowManipulation::Show, 4))
+        );
+    }
+
+    #[test]
+    fn round_trips_set_title() {
+        let intent = WindowManipulation::SetTitle("build running".to_string());
+        let sequence = intent.to_vt_sequence();
+        assert_eq!(WindowManipulation::parse(&sequence), Some((intent, sequence.len())));
+    }
+
+    #[test]
+    fn parse_returns_none_for_unrelated_bytes() {
+        assert_eq!(WindowManipulation::parse(b"hello"), None);
+    }
+
+    #[test]
+    fn scan_completes_a_sequence_split_across_two_calls() {
+        let mut pending = Vec::new();
+
+        let events = scan_for_window_manipulation(&mut pending, b"hello \x1b[");
+        assert_eq!(events, vec![TerminalEvent::Output(b"hello ".to_vec())]);
+        assert_eq!(pending, b"\x1b[");
+
+        let events = scan_for_window_manipulation(&mut pending, b"2t world");
+        assert_eq!(
+            events,
+            vec![
+                TerminalEvent::WindowManipulation(WindowManipulation::Hide),
+                TerminalEvent::Output(b" world".to_vec()),
+            ]
+        );
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn scan_completes_a_title_change_split_across_two_calls() {
+        let mut pending = Vec::new();
+        let sequence = WindowManipulation::SetTitle("done".to_string()).to_vt_sequence();
+        let (first_half, second_half) = sequence.split_at(sequence.len() / 2);
+
+        let events = scan_for_window_manipulation(&mut pending, first_half);
+        assert!(events.is_empty());
+
+        let events = scan_for_window_manipulation(&mut pending, second_half);
+        assert_eq!(
+            events,
+            vec![TerminalEvent::WindowManipulation(WindowManipulation::SetTitle(
+                "done".to_string()
+            ))]
+        );
+        assert!(pending.is_empty());
+    }
+}
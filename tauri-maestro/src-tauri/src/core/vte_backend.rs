@@ -0,0 +1,106 @@
+//! A terminal backend for hosts with no frontend VT parser of their own to
+//! forward raw bytes to.
+//!
+//! Like [`crate::core::XtermPassthroughBackend`], this backend recovers
+//! window-manipulation intents from the output stream so a host GUI can act
+//! on them; it shares the same byte-scanning [`scan_for_window_manipulation`]
+//! helper rather than a separate `vte`-crate-based parser, since the
+//! window-manipulation sequences this backend cares about are a small, fixed
+//! set that doesn't need full VT parameter parsing.
+
+use crate::core::terminal_backend::{
+    scan_for_window_manipulation, BackendCapabilities, BackendType, TerminalBackend,
+    TerminalConfig, TerminalEvent, TerminalState,
+};
+
+/// Parses the PTY output stream server-side, surfacing window-manipulation
+/// escape sequences as structured events instead of raw bytes.
+#[derive(Debug)]
+pub struct VteBackend {
+    config: TerminalConfig,
+    state: TerminalState,
+    /// An unmatched tail held back from the previous `scan()` call, in case
+    /// it's the start of a window-manipulation sequence split across reads.
+    pending: Vec<u8>,
+}
+
+impl VteBackend {
+    /// Creates a new backend for the given session configuration.
+    pub fn new(config: TerminalConfig) -> Self {
+        Self {
+            config,
+            state: TerminalState::Starting,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Returns the configuration this backend was started with.
+    pub fn config(&self) -> &TerminalConfig {
+        &self.config
+    }
+
+    /// Splits raw child output into a sequence of events, extracting
+    /// window-manipulation sequences as their own events and forwarding
+    /// everything else as `TerminalEvent::Output`. Carries an unmatched tail
+    /// across calls, since a real PTY read can split a sequence in two.
+    pub fn scan(&mut self, bytes: &[u8]) -> Vec<TerminalEvent> {
+        scan_for_window_manipulation(&mut self.pending, bytes)
+    }
+}
+
+impl TerminalBackend for VteBackend {
+    fn backend_type(&self) -> BackendType {
+        BackendType::Vte
+    }
+
+    fn capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities {
+            supports_window_manipulation: true,
+        }
+    }
+
+    fn state(&self) -> TerminalState {
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::terminal_backend::WindowManipulation;
+
+    #[test]
+    fn scan_extracts_title_change_from_surrounding_output() {
+        let mut backend = VteBackend::new(TerminalConfig::default());
+        let mut bytes = b"building... ".to_vec();
+        bytes.extend(WindowManipulation::SetTitle("done".to_string()).to_vt_sequence());
+
+        let events = backend.scan(&bytes);
+
+        assert_eq!(
+            events,
+            vec![
+                TerminalEvent::Output(b"building... ".to_vec()),
+                TerminalEvent::WindowManipulation(WindowManipulation::SetTitle("done".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_completes_a_title_change_split_across_two_reads() {
+        let mut backend = VteBackend::new(TerminalConfig::default());
+        let sequence = WindowManipulation::SetTitle("done".to_string()).to_vt_sequence();
+        let (first_half, second_half) = sequence.split_at(sequence.len() / 2);
+
+        let events = backend.scan(first_half);
+        assert!(events.is_empty());
+
+        let events = backend.scan(second_half);
+        assert_eq!(
+            events,
+            vec![TerminalEvent::WindowManipulation(WindowManipulation::SetTitle(
+                "done".to_string()
+            ))]
+        );
+    }
+}
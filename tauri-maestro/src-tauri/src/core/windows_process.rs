@@ -0,0 +1,780 @@
+//! Windows-specific process spawning utilities.
+//!
+//! Provides extension traits to apply CREATE_NO_WINDOW flag to process commands,
+//! preventing visible console windows from spawning for background operations.
+//!
+//! On Windows, spawning a process via `std::process::Command` or `tokio::process::Command`
+//! without the `CREATE_NO_WINDOW` flag causes a visible console window to appear for
+//! each subprocess. This module provides a clean, cross-platform way to hide these
+//! windows for background operations like git commands and process termination.
+
+/// The CREATE_NO_WINDOW flag for Windows process creation (0x08000000).
+/// When set, the new process does not inherit or create a console window.
+#[cfg(windows)]
+pub const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// The DETACHED_PROCESS flag for Windows process creation (0x00000008).
+/// The new process has no inherited console at all.
+#[cfg(windows)]
+pub const DETACHED_PROCESS: u32 = 0x00000008;
+
+/// The CREATE_NEW_PROCESS_GROUP flag for Windows process creation (0x00000200).
+/// The new process is the root of a new process group, so `GenerateConsoleCtrlEvent`
+/// can target it (and everything it spawns) independently of the parent's group.
+#[cfg(windows)]
+pub const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
+
+#[cfg(windows)]
+extern "system" {
+    fn GenerateConsoleCtrlEvent(dw_ctrl_event: u32, dw_process_group_id: u32) -> i32;
+}
+
+#[cfg(windows)]
+const CTRL_BREAK_EVENT: u32 = 1;
+
+#[cfg(unix)]
+extern "C" {
+    fn setsid() -> i32;
+    fn killpg(pgrp: i32, sig: i32) -> i32;
+}
+
+#[cfg(unix)]
+const SIGTERM: i32 = 15;
+
+/// Extension trait for `std::process::Command` to hide console windows on Windows.
+pub trait StdCommandExt {
+    /// Configures the command to not create a visible console window on Windows.
+    /// On non-Windows platforms, this is a no-op.
+    fn hide_console_window(&mut self) -> &mut Self;
+
+    /// Fully detaches the child from the parent's console/session, mirroring
+    /// libuv's `uv_spawn`, which sets `DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP`
+    /// on Windows. On Unix this calls `setsid()` in the child before `exec`,
+    /// giving it its own session and process group.
+    fn detached_process(&mut self) -> &mut Self;
+
+    /// Makes the child the root of a new process group, so the whole tree it
+    /// spawns can be signalled (Ctrl-Break on Windows, `killpg` on Unix)
+    /// independently of the parent's group. On Unix this is equivalent to
+    /// [`StdCommandExt::detached_process`], since `setsid()` creates both a
+    /// new session and a new process group.
+    fn new_process_group(&mut self) -> &mut Self;
+}
+
+/// Extension trait for `tokio::process::Command` to hide console windows on Windows.
+pub trait TokioCommandExt {
+    /// Configures the command to not create a visible console window on Windows.
+    /// On non-Windows platforms, this is a no-op.
+    fn hide_console_window(&mut self) -> &mut Self;
+
+    /// Fully detaches the child from the parent's console/session. See
+    /// [`StdCommandExt::detached_process`].
+    fn detached_process(&mut self) -> &mut Self;
+
+    /// Makes the child the root of a new process group. See
+    /// [`StdCommandExt::new_process_group`].
+    fn new_process_group(&mut self) -> &mut Self;
+}
+
+#[cfg(windows)]
+impl StdCommandExt for std::process::Command {
+    fn hide_console_window(&mut self) -> &mut Self {
+        use std::os::windows::process::CommandExt;
+        self.creation_flags(CREATE_NO_WINDOW)
+    }
+
+    fn detached_process(&mut self) -> &mut Self {
+        use std::os::windows::process::CommandExt;
+        self.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP)
+    }
+
+    fn new_process_group(&mut self) -> &mut Self {
+        use std::os::windows::process::CommandExt;
+        self.creation_flags(CREATE_NEW_PROCESS_GROUP)
+    }
+}
+
+#[cfg(not(windows))]
+impl StdCommandExt for std::process::Command {
+    fn hide_console_window(&mut self) -> &mut Self {
+        self // No-op on non-Windows
+    }
+
+    #[cfg(unix)]
+    fn detached_process(&mut self) -> &mut Self {
+        use std::os::unix::process::CommandExt;
+        unsafe {
+            self.pre_exec(|| {
+                if setsid() == -1 {
+                    Err(std::io::Error::last_os_error())
+                } else {
+                    Ok(())
+                }
+            })
+        }
+    }
+
+    #[cfg(unix)]
+    fn new_process_group(&mut self) -> &mut Self {
+        self.detached_process()
+    }
+
+    #[cfg(not(unix))]
+    fn detached_process(&mut self) -> &mut Self {
+        self // No-op on platforms without process groups
+    }
+
+    #[cfg(not(unix))]
+    fn new_process_group(&mut self) -> &mut Self {
+        self // No-op on platforms without process groups
+    }
+}
+
+#[cfg(windows)]
+impl TokioCommandExt for tokio::process::Command {
+    fn hide_console_window(&mut self) -> &mut Self {
+        use std::os::windows::process::CommandExt;
+        self.creation_flags(CREATE_NO_WINDOW)
+    }
+
+    fn detached_process(&mut self) -> &mut Self {
+        use std::os::windows::process::CommandExt;
+        self.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP)
+    }
+
+    fn new_process_group(&mut self) -> &mut Self {
+        use std::os::windows::process::CommandExt;
+        self.creation_flags(CREATE_NEW_PROCESS_GROUP)
+    }
+}
+
+#[cfg(not(windows))]
+impl TokioCommandExt for tokio::process::Command {
+    fn hide_console_window(&mut self) -> &mut Self {
+        self // No-op on non-Windows
+    }
+
+    #[cfg(unix)]
+    fn detached_process(&mut self) -> &mut Self {
+        unsafe {
+            self.pre_exec(|| {
+                if setsid() == -1 {
+                    Err(std::io::Error::last_os_error())
+                } else {
+                    Ok(())
+                }
+            })
+        }
+    }
+
+    #[cfg(unix)]
+    fn new_process_group(&mut self) -> &mut Self {
+        self.detached_process()
+    }
+
+    #[cfg(not(unix))]
+    fn detached_process(&mut self) -> &mut Self {
+        self // No-op on platforms without process groups
+    }
+
+    #[cfg(not(unix))]
+    fn new_process_group(&mut self) -> &mut Self {
+        self // No-op on platforms without process groups
+    }
+}
+
+/// Cross-platform handle to the process group rooted at a child spawned with
+/// [`StdCommandExt::new_process_group`]/[`TokioCommandExt::new_process_group`],
+/// used to terminate every member of the tree at once instead of orphaning
+/// grandchildren.
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessGroup {
+    id: u32,
+}
+
+impl ProcessGroup {
+    /// Captures the process group rooted at `child`. `child` must have been
+    /// spawned with `new_process_group()`/`detached_process()` for this
+    /// handle to address more than the single process.
+    pub fn from_child_id(child_id: u32) -> Self {
+        Self { id: child_id }
+    }
+
+    /// Terminates every process in the group.
+    ///
+    /// On Windows this delivers `CTRL_BREAK_EVENT` to the group created by
+    /// `CREATE_NEW_PROCESS_GROUP`. On Unix it sends `SIGTERM` to the process
+    /// group via `killpg`, relying on `setsid()` having made the child its
+    /// own process group leader (so the child's pid equals its pgid).
+    pub fn terminate(&self) -> std::io::Result<()> {
+        #[cfg(windows)]
+        {
+            if unsafe { GenerateConsoleCtrlEvent(CTRL_BREAK_EVENT, self.id) } == 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        }
+        #[cfg(unix)]
+        {
+            if unsafe { killpg(self.id as i32, SIGTERM) } != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        }
+        #[cfg(not(any(windows, unix)))]
+        {
+            Ok(())
+        }
+    }
+}
+
+/// Distinguishes hiding a child's allocated console from hiding its GUI
+/// window entirely, mirroring libuv's `UV_PROCESS_WINDOWS_HIDE` (hide the
+/// child's own window) vs. `UV_PROCESS_WINDOWS_HIDE_CONSOLE` (suppress only
+/// the allocated console), which Node wires up as two independent flags
+/// rather than collapsing them into one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WindowVisibility {
+    /// Show whatever console/window the child would normally create.
+    #[default]
+    Default,
+    /// Suppress only the allocated console (`CREATE_NO_WINDOW`); a GUI window
+    /// the child creates on its own is left alone. No-op on non-Windows.
+    HideConsole,
+    /// Suppress the console *and* any top-level GUI window the child
+    /// creates, via `STARTUPINFOW.wShowWindow = SW_HIDE`. No-op on non-Windows.
+    HideAll,
+}
+
+/// Crate-wide policy for how a manager should spawn child processes.
+///
+/// Rather than requiring every call site in [`crate::core::ProcessManager`],
+/// [`crate::core::WorktreeManager`], and [`crate::core::McpManager`] to remember
+/// to call [`StdCommandExt::hide_console_window`]/[`TokioCommandExt::hide_console_window`]
+/// by hand, a manager holds a `ProcessSpawnOptions` and applies it to every
+/// `Command` it builds. This mirrors Node's environment-level
+/// `kHideConsoleWindows` flag, which propagates hide-console behavior to all
+/// child spawns instead of forcing per-call opt-in.
+///
+/// Both the window-visibility and process-group settings are folded into a
+/// single `creation_flags` call in [`ProcessSpawnOptions::apply_to_std`]/
+/// [`ProcessSpawnOptions::apply_to_tokio`] rather than chaining the
+/// individual `StdCommandExt`/`TokioCommandExt` methods on the same command,
+/// since each of those setters *replaces* the command's creation flags
+/// rather than merging into them.
+///
+/// Defaults preserve today's behavior: console windows are shown and no
+/// process group is created unless a caller opts in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProcessSpawnOptions {
+    window_visibility: WindowVisibility,
+    process_group: bool,
+    detached: bool,
+}
+
+impl ProcessSpawnOptions {
+    /// Returns the default options (console windows are not hidden).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether spawned processes should have their console window hidden.
+    /// Shorthand for `with_window_visibility(WindowVisibility::HideConsole)`.
+    pub fn with_hide_console_window(mut self, hide: bool) -> Self {
+        self.window_visibility = if hide {
+            WindowVisibility::HideConsole
+        } else {
+            WindowVisibility::Default
+        };
+        self
+    }
+
+    /// Returns whether this policy hides at least the console window.
+    pub fn hide_console_window(&self) -> bool {
+        self.window_visibility != WindowVisibility::Default
+    }
+
+    /// Sets this policy's window-visibility mode.
+    pub fn with_window_visibility(mut self, visibility: WindowVisibility) -> Self {
+        self.window_visibility = visibility;
+        self
+    }
+
+    /// Returns this policy's window-visibility mode.
+    pub fn window_visibility(&self) -> WindowVisibility {
+        self.window_visibility
+    }
+
+    /// Sets whether spawned processes should be the root of their own
+    /// process group (see [`ProcessGroup`]).
+    pub fn with_process_group(mut self, enabled: bool) -> Self {
+        self.process_group = enabled;
+        self
+    }
+
+    /// Returns whether this policy creates a new process group.
+    pub fn process_group(&self) -> bool {
+        self.process_group
+    }
+
+    /// Sets whether spawned processes should be fully detached from this
+    /// process's console/session (`DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP`
+    /// on Windows, `setsid()` on Unix), not just made the root of a process
+    /// group. See [`StdCommandExt::detached_process`]/[`TokioCommandExt::detached_process`].
+    pub fn with_detached(mut self, enabled: bool) -> Self {
+        self.detached = enabled;
+        self
+    }
+
+    /// Returns whether this policy fully detaches spawned processes.
+    pub fn detached(&self) -> bool {
+        self.detached
+    }
+
+    /// Computes the combined Windows `creation_flags` value for this policy.
+    #[cfg(windows)]
+    fn creation_flags(&self) -> u32 {
+        let mut flags = 0;
+        if self.window_visibility != WindowVisibility::Default {
+            flags |= CREATE_NO_WINDOW;
+        }
+        if self.detached {
+            flags |= DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP;
+        } else if self.process_group {
+            flags |= CREATE_NEW_PROCESS_GROUP;
+        }
+        flags
+    }
+
+    /// Applies this policy to a `std::process::Command`.
+    pub fn apply_to_std(&self, command: &mut std::process::Command) {
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            let flags = self.creation_flags();
+            if flags != 0 {
+                command.creation_flags(flags);
+            }
+        }
+        #[cfg(unix)]
+        {
+            if self.detached {
+                command.detached_process();
+            } else if self.process_group {
+                command.new_process_group();
+            }
+        }
+    }
+
+    /// Applies this policy to a `tokio::process::Command`.
+    pub fn apply_to_tokio(&self, command: &mut tokio::process::Command) {
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            let flags = self.creation_flags();
+            if flags != 0 {
+                command.creation_flags(flags);
+            }
+        }
+        #[cfg(unix)]
+        {
+            if self.detached {
+                command.detached_process();
+            } else if self.process_group {
+                command.new_process_group();
+            }
+        }
+    }
+}
+
+/// A child process whose window-visibility policy required bypassing the
+/// ordinary `Command`-based spawn path.
+///
+/// Every variant except [`ManagedChild::Hidden`] is just a
+/// `tokio::process::Child`; `Hidden` only exists on Windows, for
+/// [`WindowVisibility::HideAll`], which needs direct control over
+/// `STARTUPINFOW.wShowWindow` that `std::process::Command` has no stable API
+/// for.
+#[derive(Debug)]
+pub enum ManagedChild {
+    /// Spawned through the normal `tokio::process::Command` path.
+    Command(tokio::process::Child),
+    /// Spawned through the raw `CreateProcessW` path to suppress a GUI window.
+    #[cfg(windows)]
+    Hidden(HiddenChild),
+}
+
+impl ManagedChild {
+    /// Returns the child's process id, if known.
+    pub fn id(&self) -> Option<u32> {
+        match self {
+            ManagedChild::Command(child) => child.id(),
+            #[cfg(windows)]
+            ManagedChild::Hidden(hidden) => Some(hidden.id()),
+        }
+    }
+}
+
+/// Raw `CreateProcessW`-based spawning used only for
+/// [`WindowVisibility::HideAll`], since `std::process::Command` has no
+/// stable way to set `STARTUPINFOW.wShowWindow`.
+#[cfg(windows)]
+mod raw {
+    use super::{CREATE_NEW_PROCESS_GROUP, CREATE_NO_WINDOW};
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+    use std::os::windows::raw::HANDLE;
+
+    #[repr(C)]
+    struct StartupInfoW {
+        cb: u32,
+        lp_reserved: *mut u16,
+        lp_desktop: *mut u16,
+        lp_title: *mut u16,
+        dw_x: u32,
+        dw_y: u32,
+        dw_x_size: u32,
+        dw_y_size: u32,
+        dw_x_count_chars: u32,
+        dw_y_count_chars: u32,
+        dw_fill_attribute: u32,
+        dw_flags: u32,
+        w_show_window: u16,
+        cb_reserved2: u16,
+        lp_reserved2: *mut u8,
+        h_std_input: HANDLE,
+        h_std_output: HANDLE,
+        h_std_error: HANDLE,
+    }
+
+    #[repr(C)]
+    struct ProcessInformation {
+        h_process: HANDLE,
+        h_thread: HANDLE,
+        dw_process_id: u32,
+        dw_thread_id: u32,
+    }
+
+    extern "system" {
+        fn CreateProcessW(
+            lp_application_name: *const u16,
+            lp_command_line: *mut u16,
+            lp_process_attributes: *mut core::ffi::c_void,
+            lp_thread_attributes: *mut core::ffi::c_void,
+            b_inherit_handles: i32,
+            dw_creation_flags: u32,
+            lp_environment: *mut core::ffi::c_void,
+            lp_current_directory: *const u16,
+            lp_startup_info: *mut StartupInfoW,
+            lp_process_information: *mut ProcessInformation,
+        ) -> i32;
+        fn CloseHandle(h_object: HANDLE) -> i32;
+        fn WaitForSingleObject(h_handle: HANDLE, dw_milliseconds: u32) -> u32;
+        fn GetExitCodeProcess(h_process: HANDLE, lp_exit_code: *mut u32) -> i32;
+        fn TerminateProcess(h_process: HANDLE, u_exit_code: u32) -> i32;
+    }
+
+    const STARTF_USESHOWWINDOW: u32 = 0x0000_0001;
+    const SW_HIDE: u16 = 0;
+    const INFINITE: u32 = u32::MAX;
+
+    fn to_wide(text: &str) -> Vec<u16> {
+        OsStr::new(text)
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect()
+    }
+
+    /// Quotes a single argument for the MSVCRT command-line parser used by
+    /// `CreateProcessW`, following the standard algorithm (doubling a run of
+    /// backslashes when it precedes a quote, since a lone backslash there
+    /// would otherwise escape the closing quote instead of terminating the
+    /// argument — easy to hit for a trailing-backslash directory path).
+    fn quote_arg(arg: &str) -> String {
+        let needs_quotes = arg.is_empty() || arg.contains([' ', '\t', '"']);
+        if !needs_quotes {
+            return arg.to_string();
+        }
+
+        let mut quoted = String::with_capacity(arg.len() + 2);
+        quoted.push('"');
+        let mut chars = arg.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '\\' {
+                let mut backslashes = 1;
+                while chars.peek() == Some(&'\\') {
+                    chars.next();
+                    backslashes += 1;
+                }
+                match chars.peek() {
+                    Some('"') | None => quoted.push_str(&"\\".repeat(backslashes * 2)),
+                    _ => quoted.push_str(&"\\".repeat(backslashes)),
+                }
+            } else if c == '"' {
+                quoted.push('\\');
+                quoted.push('"');
+            } else {
+                quoted.push(c);
+            }
+        }
+        quoted.push('"');
+        quoted
+    }
+
+    /// A child process spawned via a raw `CreateProcessW` call with
+    /// `STARTUPINFOW.wShowWindow = SW_HIDE`, so that neither a console nor a
+    /// top-level GUI window becomes visible. It is also the root of its own
+    /// process group (`CREATE_NEW_PROCESS_GROUP`), so it can be captured in a
+    /// [`super::ProcessGroup`] like any other tracked child, including for
+    /// restart.
+    #[derive(Debug)]
+    pub struct HiddenChild {
+        process: HANDLE,
+        pid: u32,
+    }
+
+    // SAFETY: `HANDLE` is just a `HANDLE` value owned exclusively by this
+    // struct; Win32 handles may be used from any thread.
+    unsafe impl Send for HiddenChild {}
+
+    impl HiddenChild {
+        /// Spawns `program` with `args` as a fully hidden process: no
+        /// console (`CREATE_NO_WINDOW`) and no visible top-level window
+        /// (`STARTUPINFOW.wShowWindow = SW_HIDE`), as the root of its own
+        /// process group (`CREATE_NEW_PROCESS_GROUP`).
+        pub fn spawn(program: &str, args: &[String]) -> std::io::Result<Self> {
+            let mut command_line = quote_arg(program);
+            for arg in args {
+                command_line.push(' ');
+                command_line.push_str(&quote_arg(arg));
+            }
+            let mut command_line = to_wide(&command_line);
+
+            let mut startup_info: StartupInfoW = unsafe { std::mem::zeroed() };
+            startup_info.cb = std::mem::size_of::<StartupInfoW>() as u32;
+            startup_info.dw_flags = STARTF_USESHOWWINDOW;
+            startup_info.w_show_window = SW_HIDE;
+
+            let mut process_information: ProcessInformation = unsafe { std::mem::zeroed() };
+
+            let ok = unsafe {
+                CreateProcessW(
+                    std::ptr::null(),
+                    command_line.as_mut_ptr(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    0,
+                    CREATE_NO_WINDOW | CREATE_NEW_PROCESS_GROUP,
+                    std::ptr::null_mut(),
+                    std::ptr::null(),
+                    &mut startup_info,
+                    &mut process_information,
+                )
+            };
+
+            if ok == 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+
+            unsafe { CloseHandle(process_information.h_thread) };
+
+            Ok(Self {
+                process: process_information.h_process,
+                pid: process_information.dw_process_id,
+            })
+        }
+
+        /// Returns the child's process id, which also identifies the
+        /// process group it roots (`CREATE_NEW_PROCESS_GROUP`).
+        pub fn id(&self) -> u32 {
+            self.pid
+        }
+
+        /// Blocks the current thread until the child exits, returning its exit code.
+        pub fn wait(&self) -> std::io::Result<u32> {
+            if unsafe { WaitForSingleObject(self.process, INFINITE) } == u32::MAX {
+                return Err(std::io::Error::last_os_error());
+            }
+            let mut exit_code = 0u32;
+            if unsafe { GetExitCodeProcess(self.process, &mut exit_code) } == 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(exit_code)
+        }
+
+        /// Forcibly terminates the child.
+        pub fn kill(&self) -> std::io::Result<()> {
+            if unsafe { TerminateProcess(self.process, 1) } == 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        }
+    }
+
+    impl Drop for HiddenChild {
+        fn drop(&mut self) {
+            unsafe { CloseHandle(self.process) };
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::quote_arg;
+
+        #[test]
+        fn quote_arg_leaves_simple_arguments_unquoted() {
+            assert_eq!(quote_arg("simple"), "simple");
+        }
+
+        #[test]
+        fn quote_arg_quotes_arguments_containing_spaces() {
+            assert_eq!(quote_arg("two words"), "\"two words\"");
+        }
+
+        #[test]
+        fn quote_arg_doubles_a_trailing_backslash_before_the_closing_quote() {
+            assert_eq!(quote_arg("C:\\Program Files\\"), "\"C:\\Program Files\\\\\"");
+        }
+
+        #[test]
+        fn quote_arg_does_not_double_a_backslash_not_followed_by_a_quote() {
+            assert_eq!(quote_arg("C:\\Program Files\\sub"), "\"C:\\Program Files\\sub\"");
+        }
+
+        #[test]
+        fn quote_arg_escapes_embedded_quotes() {
+            assert_eq!(quote_arg("say \"hi\""), "\"say \\\"hi\\\"\"");
+        }
+    }
+}
+
+#[cfg(windows)]
+pub use raw::HiddenChild;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_options_do_not_hide_console() {
+        let options = ProcessSpawnOptions::new();
+        assert!(!options.hide_console_window());
+    }
+
+    #[test]
+    fn builder_enables_hide_console_window() {
+        let options = ProcessSpawnOptions::new().with_hide_console_window(true);
+        assert!(options.hide_console_window());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn apply_to_std_sets_creation_flags_when_enabled() {
+        let options = ProcessSpawnOptions::new().with_hide_console_window(true);
+        let mut command = std::process::Command::new("cmd");
+        options.apply_to_std(&mut command);
+        assert_eq!(
+            std::os::windows::process::CommandExt::creation_flags(&command) & CREATE_NO_WINDOW,
+            CREATE_NO_WINDOW
+        );
+    }
+
+    #[tokio::test]
+    async fn process_manager_dummy_spawn_respects_hide_console_policy() {
+        use crate::core::ProcessManager;
+
+        let manager = ProcessManager::new(ProcessSpawnOptions::new().with_hide_console_window(true));
+        let status = manager
+            .spawn_dummy()
+            .await
+            .expect("dummy process should spawn");
+        assert!(status.success());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn detached_process_sets_detached_and_group_flags() {
+        let mut command = std::process::Command::new("cmd");
+        command.detached_process();
+        let flags = std::os::windows::process::CommandExt::creation_flags(&command);
+        assert_eq!(flags & DETACHED_PROCESS, DETACHED_PROCESS);
+        assert_eq!(flags & CREATE_NEW_PROCESS_GROUP, CREATE_NEW_PROCESS_GROUP);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn new_process_group_sets_only_the_group_flag() {
+        let mut command = std::process::Command::new("cmd");
+        command.new_process_group();
+        let flags = std::os::windows::process::CommandExt::creation_flags(&command);
+        assert_eq!(flags & CREATE_NEW_PROCESS_GROUP, CREATE_NEW_PROCESS_GROUP);
+        assert_eq!(flags & DETACHED_PROCESS, 0);
+    }
+
+    #[tokio::test]
+    async fn process_manager_spawn_tracked_returns_a_terminable_group() {
+        use crate::core::ProcessManager;
+
+        let manager = ProcessManager::new(ProcessSpawnOptions::new());
+        #[cfg(windows)]
+        let (program, args) = ("cmd", vec!["/C".to_string(), "pause".to_string()]);
+        #[cfg(not(windows))]
+        let (program, args) = ("sleep", vec!["5".to_string()]);
+
+        let (mut child, group) = manager
+            .spawn_tracked(program, &args)
+            .expect("tracked process should spawn");
+        group.terminate().expect("group should terminate");
+        let status = child.wait().await.expect("child should exit after terminate");
+        assert!(!status.success());
+    }
+
+    #[test]
+    fn default_window_visibility_does_not_hide_console() {
+        let options = ProcessSpawnOptions::new();
+        assert_eq!(options.window_visibility(), WindowVisibility::Default);
+        assert!(!options.hide_console_window());
+    }
+
+    #[test]
+    fn hide_all_is_reported_as_hiding_the_console_too() {
+        let options = ProcessSpawnOptions::new().with_window_visibility(WindowVisibility::HideAll);
+        assert!(options.hide_console_window());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn apply_to_std_combines_console_and_group_flags_in_one_call() {
+        let options = ProcessSpawnOptions::new()
+            .with_window_visibility(WindowVisibility::HideConsole)
+            .with_process_group(true);
+        let mut command = std::process::Command::new("cmd");
+        options.apply_to_std(&mut command);
+        let flags = std::os::windows::process::CommandExt::creation_flags(&command);
+        assert_eq!(flags & CREATE_NO_WINDOW, CREATE_NO_WINDOW);
+        assert_eq!(flags & CREATE_NEW_PROCESS_GROUP, CREATE_NEW_PROCESS_GROUP);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn apply_to_std_detached_sets_detached_and_group_flags() {
+        let options = ProcessSpawnOptions::new().with_detached(true);
+        let mut command = std::process::Command::new("cmd");
+        options.apply_to_std(&mut command);
+        let flags = std::os::windows::process::CommandExt::creation_flags(&command);
+        assert_eq!(flags & DETACHED_PROCESS, DETACHED_PROCESS);
+        assert_eq!(flags & CREATE_NEW_PROCESS_GROUP, CREATE_NEW_PROCESS_GROUP);
+    }
+
+    #[cfg(windows)]
+    #[tokio::test]
+    async fn spawn_with_visibility_hide_all_suppresses_console_and_window() {
+        use crate::core::ProcessManager;
+
+        let manager = ProcessManager::new(ProcessSpawnOptions::new());
+        let child = manager
+            .spawn_with_visibility("cmd", &["/C".to_string(), "exit".to_string(), "0".to_string()], WindowVisibility::HideAll)
+            .expect("hidden process should spawn");
+        assert!(child.id().is_some());
+    }
+}
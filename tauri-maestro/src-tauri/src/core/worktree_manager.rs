@@ -0,0 +1,78 @@
+//! Creates and tears down git worktrees used to isolate concurrent sessions.
+//!
+//! `WorktreeManager` shells out to `git worktree` so each session can work in
+//! its own checkout without disturbing the user's primary working tree. Every
+//! `git` invocation goes through [`WorktreeManager::git_command`], which applies
+//! the manager's [`ProcessSpawnOptions`] so a forgotten spawn site can't flash a
+//! console window on Windows GUI embeds.
+
+use crate::core::windows_process::ProcessSpawnOptions;
+use std::path::Path;
+use std::process::Command;
+
+/// Creates and removes git worktrees on behalf of terminal sessions.
+#[derive(Debug, Clone)]
+pub struct WorktreeManager {
+    spawn_options: ProcessSpawnOptions,
+}
+
+impl WorktreeManager {
+    /// Creates a new `WorktreeManager` that applies `spawn_options` to every
+    /// `git` command it runs.
+    pub fn new(spawn_options: ProcessSpawnOptions) -> Self {
+        Self { spawn_options }
+    }
+
+    /// Builds a `git` command rooted at `repo_path`, applying this manager's
+    /// spawn policy.
+    fn git_command(&self, repo_path: &Path) -> Command {
+        let mut command = Command::new("git");
+        command.current_dir(repo_path);
+        self.spawn_options.apply_to_std(&mut command);
+        command
+    }
+
+    /// Adds a new worktree at `worktree_path` on `branch`, rooted at `repo_path`.
+    pub fn add_worktree(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        branch: &str,
+    ) -> std::io::Result<std::process::ExitStatus> {
+        self.git_command(repo_path)
+            .arg("worktree")
+            .arg("add")
+            .arg(worktree_path)
+            .arg(branch)
+            .status()
+    }
+
+    /// Removes the worktree at `worktree_path`, rooted at `repo_path`.
+    pub fn remove_worktree(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+    ) -> std::io::Result<std::process::ExitStatus> {
+        self.git_command(repo_path)
+            .arg("worktree")
+            .arg("remove")
+            .arg(worktree_path)
+            .status()
+    }
+}
+
+#[cfg(all(test, windows))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn git_command_applies_this_managers_hide_console_policy() {
+        let manager = WorktreeManager::new(ProcessSpawnOptions::new().with_hide_console_window(true));
+        let command = manager.git_command(Path::new("."));
+        assert_eq!(
+            std::os::windows::process::CommandExt::creation_flags(&command)
+                & crate::core::windows_process::CREATE_NO_WINDOW,
+            crate::core::windows_process::CREATE_NO_WINDOW
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! A passthrough terminal backend that forwards raw PTY output to an
+//! xterm.js frontend, doing just enough server-side interception to recover
+//! window-manipulation intents the frontend itself has no window to act on.
+
+use crate::core::terminal_backend::{
+    scan_for_window_manipulation, BackendCapabilities, BackendType, TerminalBackend,
+    TerminalConfig, TerminalEvent, TerminalState,
+};
+
+/// Forwards raw PTY bytes to an xterm.js frontend, intercepting
+/// window-manipulation escape sequences along the way so a host GUI can act
+/// on them even though the frontend has no real window to manipulate.
+#[derive(Debug)]
+pub struct XtermPassthroughBackend {
+    config: TerminalConfig,
+    state: TerminalState,
+    /// An unmatched tail held back from the previous `scan()` call, in case
+    /// it's the start of a window-manipulation sequence split across reads.
+    pending: Vec<u8>,
+}
+
+impl XtermPassthroughBackend {
+    /// Creates a new backend for the given session configuration.
+    pub fn new(config: TerminalConfig) -> Self {
+        Self {
+            config,
+            state: TerminalState::Starting,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Returns the configuration this backend was started with.
+    pub fn config(&self) -> &TerminalConfig {
+        &self.config
+    }
+
+    /// Splits raw child output into a sequence of events: window-manipulation
+    /// sequences are extracted as their own events, and every other byte is
+    /// still forwarded to the frontend as `TerminalEvent::Output` so xterm.js
+    /// renders it unchanged. Carries an unmatched tail across calls, since a
+    /// real PTY read can split a sequence in two.
+    pub fn scan(&mut self, bytes: &[u8]) -> Vec<TerminalEvent> {
+        scan_for_window_manipulation(&mut self.pending, bytes)
+    }
+}
+
+impl TerminalBackend for XtermPassthroughBackend {
+    fn backend_type(&self) -> BackendType {
+        BackendType::XtermPassthrough
+    }
+
+    fn capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities {
+            supports_window_manipulation: true,
+        }
+    }
+
+    fn state(&self) -> TerminalState {
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::terminal_backend::WindowManipulation;
+
+    #[test]
+    fn scan_extracts_window_manipulation_from_surrounding_output() {
+        let mut backend = XtermPassthroughBackend::new(TerminalConfig::default());
+        let mut bytes = b"hello ".to_vec();
+        bytes.extend(WindowManipulation::Hide.to_vt_sequence());
+        bytes.extend(b" world");
+
+        let events = backend.scan(&bytes);
+
+        assert_eq!(
+            events,
+            vec![
+                TerminalEvent::Output(b"hello ".to_vec()),
+                TerminalEvent::WindowManipulation(WindowManipulation::Hide),
+                TerminalEvent::Output(b" world".to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_completes_a_sequence_split_across_two_reads() {
+        let mut backend = XtermPassthroughBackend::new(TerminalConfig::default());
+
+        let events = backend.scan(b"building \x1b[");
+        assert_eq!(events, vec![TerminalEvent::Output(b"building ".to_vec())]);
+
+        let events = backend.scan(b"2t done");
+        assert_eq!(
+            events,
+            vec![
+                TerminalEvent::WindowManipulation(WindowManipulation::Hide),
+                TerminalEvent::Output(b" done".to_vec()),
+            ]
+        );
+    }
+}